@@ -0,0 +1,292 @@
+//! A persistent cache of file digests keyed by canonical path, size and modification time.
+//!
+//! Re-hashing a large, rarely-changing directory tree is wasteful. [`HashCache`] remembers the
+//! digest computed for a file the last time it was seen at a given length and mtime, so
+//! [`chksum_cached`] can skip reading a file entirely on a hit while still recursing into every
+//! directory to detect changes anywhere in the tree.
+
+use std::collections::HashMap;
+use std::fs::{self, metadata, read_dir};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::{Chksumable, Error, Hash, Hashable, Result};
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn decode_hex(text: &str) -> Result<Vec<u8>> {
+    // work over bytes, not `&str` byte ranges: a malformed cache file is not guaranteed to be
+    // ASCII, and slicing on a non-char-boundary index would panic instead of erroring
+    let bytes = text.as_bytes();
+    if !bytes.is_ascii() {
+        return Err(Error::Cache("digest contains non-ascii bytes".into()));
+    }
+    if bytes.len() % 2 != 0 {
+        return Err(Error::Cache("digest has odd length".into()));
+    }
+    bytes
+        .chunks_exact(2)
+        .map(|pair| {
+            let pair = std::str::from_utf8(pair).expect("validated ascii");
+            u8::from_str_radix(pair, 16).map_err(|error| Error::Cache(format!("invalid hex digit: {error}")))
+        })
+        .collect()
+}
+
+struct CacheEntry {
+    len: u64,
+    mtime_nanos: u128,
+    digest: Vec<u8>,
+}
+
+/// An in-memory hash cache that can be persisted to and loaded from a simple line-oriented
+/// on-disk format.
+#[derive(Default)]
+pub struct HashCache {
+    entries: HashMap<PathBuf, CacheEntry>,
+}
+
+impl HashCache {
+    /// Creates an empty cache.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a cache previously written by [`HashCache::flush`].
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let file = fs::File::open(path).map_err(Error::Io)?;
+        let mut cache = Self::new();
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(Error::Io)?;
+            if line.is_empty() {
+                continue;
+            }
+            let mut fields = line.splitn(4, '\t');
+            let digest = fields.next().ok_or_else(|| Error::Cache("missing digest field".into()))?;
+            let len = fields.next().ok_or_else(|| Error::Cache("missing length field".into()))?;
+            let mtime_nanos = fields.next().ok_or_else(|| Error::Cache("missing mtime field".into()))?;
+            let path = fields.next().ok_or_else(|| Error::Cache("missing path field".into()))?;
+
+            let digest = decode_hex(digest)?;
+            let len = len
+                .parse()
+                .map_err(|error| Error::Cache(format!("invalid length field: {error}")))?;
+            let mtime_nanos = mtime_nanos
+                .parse()
+                .map_err(|error| Error::Cache(format!("invalid mtime field: {error}")))?;
+
+            cache.entries.insert(PathBuf::from(path), CacheEntry {
+                len,
+                mtime_nanos,
+                digest,
+            });
+        }
+        Ok(cache)
+    }
+
+    /// Persists the cache to `path`, overwriting any existing file.
+    pub fn flush(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut file = fs::File::create(path).map_err(Error::Io)?;
+        for (path, entry) in &self.entries {
+            writeln!(
+                file,
+                "{}\t{}\t{}\t{}",
+                encode_hex(&entry.digest),
+                entry.len,
+                entry.mtime_nanos,
+                path.display()
+            )
+            .map_err(Error::Io)?;
+        }
+        Ok(())
+    }
+
+    /// Removes entries whose file no longer exists.
+    pub fn prune(&mut self) {
+        self.entries.retain(|path, _| path.exists());
+    }
+}
+
+fn mtime_nanos(metadata: &fs::Metadata) -> Result<u128> {
+    let modified = metadata.modified().map_err(Error::Io)?;
+    let since_epoch = modified.duration_since(UNIX_EPOCH).unwrap_or_default();
+    Ok(since_epoch.as_nanos())
+}
+
+/// Computes the hash of the regular file at `path`, consulting `cache` first.
+///
+/// On a cache hit (same canonical path, length and modification time as a prior call), the
+/// stored digest bytes are returned without reading the file. On a miss, the file is hashed
+/// normally and the result is recorded in `cache`.
+fn chksum_cached_file<T>(path: &Path, cache: &mut HashCache) -> Result<Vec<u8>>
+where
+    T: Hash,
+    T::Digest: AsRef<[u8]>,
+{
+    let canonical = path.canonicalize().map_err(Error::Io)?;
+    let file_metadata = metadata(&canonical).map_err(Error::Io)?;
+    let len = file_metadata.len();
+    let mtime_nanos = mtime_nanos(&file_metadata)?;
+
+    if let Some(entry) = cache.entries.get(&canonical) {
+        if entry.len == len && entry.mtime_nanos == mtime_nanos {
+            return Ok(entry.digest.clone());
+        }
+    }
+
+    let mut file = canonical.as_path();
+    let digest = file.chksum::<T>()?;
+    let digest = digest.as_bytes().to_vec();
+    cache.entries.insert(canonical, CacheEntry {
+        len,
+        mtime_nanos,
+        digest: digest.clone(),
+    });
+    Ok(digest)
+}
+
+/// Computes the hash of `path`, consulting `cache` for every regular file it contains.
+///
+/// A directory is hashed by recursing into its entries, sorted by path, and folding each
+/// entry's digest bytes into the directory's own hash — so a changed file deep in the tree
+/// changes the digest of every ancestor directory above it. Only regular files are ever looked
+/// up in or recorded into `cache`; a directory's own `(len, mtime)` is not a reliable signal of
+/// its content having changed.
+pub fn chksum_cached<T>(path: impl AsRef<Path>, cache: &mut HashCache) -> Result<Vec<u8>>
+where
+    T: Hash,
+    T::Digest: AsRef<[u8]>,
+{
+    let path = path.as_ref();
+    let metadata = metadata(path).map_err(Error::Io)?;
+    if !metadata.is_dir() {
+        return chksum_cached_file::<T>(path, cache);
+    }
+
+    let mut entries: Vec<_> = read_dir(path)
+        .map_err(Error::Io)?
+        .collect::<std::io::Result<_>>()
+        .map_err(Error::Io)?;
+    entries.sort_by_key(fs::DirEntry::path);
+
+    let mut hash = T::default();
+    for entry in entries {
+        let digest = chksum_cached::<T>(entry.path(), cache)?;
+        digest.hash_with(&mut hash);
+    }
+    Ok(hash.digest().as_bytes().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::test_util::TestHash;
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("chksum-core-cache-test-{name}-{}", std::process::id()));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+
+        fn path(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn decode_hex_rejects_non_ascii_without_panicking() {
+        // a byte-range slice on this `&str` at index 2 would land inside the 2-byte UTF-8
+        // encoding of 'é' and panic instead of erroring
+        let result = decode_hex("a\u{e9}a");
+        assert!(matches!(result, Err(Error::Cache(_))));
+    }
+
+    #[test]
+    fn decode_hex_rejects_odd_length() {
+        let result = decode_hex("abc");
+        assert!(matches!(result, Err(Error::Cache(_))));
+    }
+
+    #[test]
+    fn hex_roundtrips() {
+        let bytes = vec![0x00, 0x7F, 0xFF, 0x10];
+        assert_eq!(decode_hex(&encode_hex(&bytes)).unwrap(), bytes);
+    }
+
+    #[test]
+    fn hash_cache_flush_then_load_roundtrips() {
+        let dir = TempDir::new("roundtrip");
+        let cache_path = dir.path().join("cache");
+
+        let mut cache = HashCache::new();
+        cache.entries.insert(dir.path().join("a.txt"), CacheEntry {
+            len: 5,
+            mtime_nanos: 123,
+            digest: vec![0xDE, 0xAD, 0xBE, 0xEF],
+        });
+        cache.flush(&cache_path).unwrap();
+
+        let loaded = HashCache::load(&cache_path).unwrap();
+        let entry = loaded.entries.get(&dir.path().join("a.txt")).unwrap();
+        assert_eq!(entry.len, 5);
+        assert_eq!(entry.mtime_nanos, 123);
+        assert_eq!(entry.digest, vec![0xDE, 0xAD, 0xBE, 0xEF]);
+    }
+
+    #[test]
+    fn prune_removes_entries_for_deleted_files() {
+        let dir = TempDir::new("prune");
+        let present = dir.path().join("present.txt");
+        let missing = dir.path().join("missing.txt");
+        fs::write(&present, b"data").unwrap();
+
+        let mut cache = HashCache::new();
+        cache.entries.insert(present.clone(), CacheEntry {
+            len: 4,
+            mtime_nanos: 0,
+            digest: vec![],
+        });
+        cache.entries.insert(missing, CacheEntry {
+            len: 0,
+            mtime_nanos: 0,
+            digest: vec![],
+        });
+
+        cache.prune();
+
+        assert_eq!(cache.entries.len(), 1);
+        assert!(cache.entries.contains_key(&present));
+    }
+
+    #[test]
+    fn chksum_cached_detects_changes_to_a_file_nested_inside_a_directory() {
+        let dir = TempDir::new("nested-change");
+        let nested = dir.path().join("sub").join("file.txt");
+        fs::create_dir_all(nested.parent().unwrap()).unwrap();
+        fs::write(&nested, b"first").unwrap();
+
+        let mut cache = HashCache::new();
+        let before = chksum_cached::<TestHash>(dir.path(), &mut cache).unwrap();
+
+        fs::write(&nested, b"second, and longer").unwrap();
+        let after = chksum_cached::<TestHash>(dir.path(), &mut cache).unwrap();
+
+        assert_ne!(before, after);
+    }
+}
@@ -0,0 +1,170 @@
+//! Transparent decompression support for [`Chksumable`] inputs.
+//!
+//! [`Decompressed`] wraps any reader and, before the first read, sniffs a handful of bytes to
+//! detect a known compression format (gzip, zstd, bzip2 or xz). If a magic number is recognized
+//! the matching streaming decoder is used to produce the bytes that get fed into the hash;
+//! otherwise the stream is hashed as-is.
+
+use std::io::{self, BufRead, BufReader, Read};
+
+use bzip2::read::BzDecoder;
+use flate2::read::GzDecoder;
+use xz2::read::XzDecoder;
+use zstd::stream::read::Decoder as ZstdDecoder;
+
+use crate::{Chksumable, Error, Hash, Hashable, Result};
+
+const GZIP_MAGIC: [u8; 2] = [0x1F, 0x8B];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+const BZIP2_MAGIC: [u8; 3] = [0x42, 0x5A, 0x68];
+const XZ_MAGIC: [u8; 5] = [0xFD, 0x37, 0x7A, 0x58, 0x5A];
+
+enum Decoder<R: BufRead> {
+    Gzip(GzDecoder<R>),
+    Zstd(Box<ZstdDecoder<'static, R>>),
+    Bzip2(BzDecoder<R>),
+    Xz(XzDecoder<R>),
+    Raw(R),
+}
+
+impl<R: BufRead> Read for Decoder<R> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Gzip(reader) => reader.read(buffer),
+            Self::Zstd(reader) => reader.read(buffer),
+            Self::Bzip2(reader) => reader.read(buffer),
+            Self::Xz(reader) => reader.read(buffer),
+            Self::Raw(reader) => reader.read(buffer),
+        }
+    }
+}
+
+fn detect<R>(mut reader: BufReader<R>) -> Result<Decoder<BufReader<R>>>
+where
+    R: Read,
+{
+    let buffer = reader.fill_buf().map_err(Error::Io)?;
+    let decoder = if buffer.starts_with(&GZIP_MAGIC) {
+        Decoder::Gzip(GzDecoder::new(reader))
+    } else if buffer.starts_with(&ZSTD_MAGIC) {
+        let decoder = ZstdDecoder::with_buffer(reader).map_err(Error::Decompression)?;
+        Decoder::Zstd(Box::new(decoder))
+    } else if buffer.starts_with(&BZIP2_MAGIC) {
+        Decoder::Bzip2(BzDecoder::new(reader))
+    } else if buffer.starts_with(&XZ_MAGIC) {
+        Decoder::Xz(XzDecoder::new(reader))
+    } else {
+        Decoder::Raw(reader)
+    };
+    Ok(decoder)
+}
+
+/// Classifies a read failure from a (possibly decompressing) reader as either a rejected
+/// compressed stream or a genuine I/O failure.
+///
+/// Errors of kind `io::ErrorKind::InvalidInput` (how `flate2` reports an invalid gzip header or
+/// a corrupt deflate stream) or `io::ErrorKind::InvalidData` become [`Error::Decompression`];
+/// any other kind (a broken pipe, a failing disk read, ...) is returned as [`Error::Io`]. `zstd`
+/// reports its decoding errors with `io::ErrorKind::Other`, so those are returned as
+/// [`Error::Io`] as well.
+fn classify_error(error: io::Error) -> Error {
+    if matches!(error.kind(), io::ErrorKind::InvalidInput | io::ErrorKind::InvalidData) {
+        Error::Decompression(error)
+    } else {
+        Error::Io(error)
+    }
+}
+
+/// Wraps a reader so that a recognized compressed stream is transparently decompressed before
+/// being hashed.
+///
+/// Supported formats are gzip, zstd, bzip2 and xz, detected by their magic numbers. A stream
+/// that does not match any of them is hashed unchanged.
+pub struct Decompressed<R> {
+    reader: Option<BufReader<R>>,
+}
+
+impl<R> Decompressed<R>
+where
+    R: Read,
+{
+    /// Wraps `reader` so that it is transparently decompressed when checksummed.
+    #[must_use]
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader: Some(BufReader::new(reader)),
+        }
+    }
+}
+
+impl<R> Chksumable for Decompressed<R>
+where
+    R: Read,
+{
+    fn chksum_with<H>(&mut self, hash: &mut H) -> Result<()>
+    where
+        H: Hash,
+    {
+        let reader = self.reader.take().expect("reader already consumed");
+        let mut decoder = detect(reader)?;
+        let mut buffer = [0; 8192];
+        loop {
+            let length = decoder.read(&mut buffer).map_err(classify_error)?;
+            if length == 0 {
+                break;
+            }
+            buffer[..length].hash_with(hash);
+        }
+        Ok(())
+    }
+}
+
+/// Computes the hash of the given reader, transparently decompressing it first.
+///
+/// See [`Decompressed`] for the list of supported formats and the fallback behavior.
+pub fn chksum_decompressed<T, R>(reader: R) -> Result<T::Digest>
+where
+    T: Hash,
+    R: Read,
+{
+    Decompressed::new(reader).chksum::<T>()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write as _};
+
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    use super::*;
+    use crate::test_util::TestHash;
+
+    #[test]
+    fn raw_passthrough_when_no_magic_matches() {
+        let plain = b"just some plain, uncompressed bytes";
+        let digest = chksum_decompressed::<TestHash, _>(Cursor::new(plain.as_slice())).unwrap();
+        assert_eq!(digest, TestHash::hash(plain.as_slice()));
+    }
+
+    #[test]
+    fn gzip_stream_is_decompressed_before_hashing() {
+        let original = b"hello, this is the decompressed content";
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(original).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let digest = chksum_decompressed::<TestHash, _>(Cursor::new(compressed)).unwrap();
+        assert_eq!(digest, TestHash::hash(original.as_slice()));
+    }
+
+    #[test]
+    fn corrupted_gzip_stream_surfaces_as_decompression_error() {
+        let mut corrupt = GZIP_MAGIC.to_vec();
+        corrupt.extend_from_slice(&[0; 32]);
+
+        let result = chksum_decompressed::<TestHash, _>(Cursor::new(corrupt));
+
+        assert!(matches!(result, Err(Error::Decompression(_))));
+    }
+}
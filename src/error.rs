@@ -9,6 +9,18 @@ pub enum Error {
     /// The I/O error occured.
     #[error(transparent)]
     Io(#[from] io::Error),
+    /// A decompression operation failed.
+    #[cfg(feature = "decompression")]
+    #[error("decompression failed: {0}")]
+    Decompression(io::Error),
+    /// The tar archive is malformed or uses an unsupported feature.
+    #[cfg(feature = "tar")]
+    #[error("malformed tar archive: {0}")]
+    Tar(String),
+    /// The on-disk hash cache is malformed.
+    #[cfg(feature = "cache")]
+    #[error("malformed hash cache entry: {0}")]
+    Cache(String),
 }
 
 /// A specialized [`Result`](std::result::Result) type for checksum-based operations.
@@ -20,8 +20,31 @@
 //! ## Asynchronous Runtime
 //!
 //! * `async-runtime-tokio`: Enables async interface for Tokio runtime.
+//! * `async-runtime-tokio-uring`: Enables an `io_uring`-backed async interface via
+//!   `tokio-uring`, for faster large-file and many-file hashing on Linux.
 //!
-//! By default, neither of these features is enabled.
+//! ## Decompression
+//!
+//! * `decompression`: Enables transparent decompression of gzip, zstd, bzip2 and xz streams via
+//!   [`Decompressed`] and [`chksum_decompressed`]. Combine with `async-runtime-tokio` for the
+//!   async equivalents.
+//!
+//! ## Tar Archives
+//!
+//! * `tar`: Enables deterministic hashing of tar archives via [`Tar`] and [`chksum_tar`].
+//!
+//! ## Hash Cache
+//!
+//! * `cache`: Enables [`HashCache`], a persistent cache of file digests keyed by path, length
+//!   and modification time, consulted via [`chksum_cached`].
+//!
+//! ## Parallelism
+//!
+//! * `rayon`: Enables [`par_chksum`], which hashes the files of a directory tree concurrently
+//!   on a `rayon` thread pool and combines the per-file digests deterministically. Combine with
+//!   `async-runtime-tokio` for the `tokio::spawn`-backed async equivalent.
+//!
+//! By default, none of these features is enabled.
 //!
 //! # Example Crates
 //!
@@ -41,9 +64,22 @@
 
 #![forbid(unsafe_code)]
 
+#[cfg(feature = "cache")]
+mod cache;
+#[cfg(feature = "decompression")]
+mod decompress;
 mod error;
+mod merkle;
+#[cfg(test)]
+pub(crate) mod test_util;
+#[cfg(feature = "rayon")]
+mod par;
+#[cfg(feature = "tar")]
+mod tar;
 #[cfg(feature = "async-runtime-tokio")]
 mod tokio;
+#[cfg(feature = "async-runtime-tokio-uring")]
+mod uring;
 
 use std::fmt::{Display, LowerHex, UpperHex};
 use std::fs::{read_dir, DirEntry, File, ReadDir};
@@ -55,7 +91,20 @@ use async_trait::async_trait;
 #[doc(no_inline)]
 pub use chksum_hash_core as hash;
 
+#[cfg(feature = "cache")]
+pub use crate::cache::{chksum_cached, HashCache};
+#[cfg(feature = "decompression")]
+pub use crate::decompress::{chksum_decompressed, Decompressed};
 pub use crate::error::{Error, Result};
+pub use crate::merkle::merkle_chksum;
+#[cfg(feature = "rayon")]
+pub use crate::par::par_chksum;
+#[cfg(feature = "tar")]
+pub use crate::tar::{chksum_tar, Tar};
+#[cfg(all(feature = "async-runtime-tokio", feature = "decompression"))]
+pub use crate::tokio::{async_chksum_decompressed, AsyncDecompressed};
+#[cfg(all(feature = "async-runtime-tokio", feature = "rayon"))]
+pub use crate::tokio::async_par_chksum;
 
 /// Creates a default hash.
 #[must_use]
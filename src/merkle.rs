@@ -0,0 +1,172 @@
+//! Content-addressed directory digests.
+//!
+//! The [`Chksumable`] implementation for [`ReadDir`](std::fs::ReadDir) concatenates every
+//! file's bytes into a single hash, which cannot tell which subtree changed and ignores names
+//! and file modes entirely. [`merkle_chksum`] instead builds a digest per filesystem node: a
+//! regular file's node digest is the hash of its bytes, and a directory's node digest binds in
+//! the name, type and mode of every entry together with that entry's own digest, so the result
+//! is invariant to directory-iteration order but sensitive to renames, mode changes and moves.
+
+use std::fs::{read_dir, symlink_metadata};
+use std::path::{Path, PathBuf};
+
+use crate::{Chksumable, Hash, Hashable, Result};
+
+const TYPE_FILE: u8 = 0;
+const TYPE_DIR: u8 = 1;
+const TYPE_SYMLINK: u8 = 2;
+
+#[cfg(unix)]
+fn mode_of(metadata: &std::fs::Metadata) -> u32 {
+    use std::os::unix::fs::MetadataExt as _;
+    metadata.mode() & 0o7777
+}
+
+#[cfg(not(unix))]
+fn mode_of(_metadata: &std::fs::Metadata) -> u32 {
+    0
+}
+
+fn node<T>(path: &Path, nodes: &mut Vec<(PathBuf, Vec<u8>)>) -> Result<T::Digest>
+where
+    T: Hash,
+    T::Digest: AsRef<[u8]>,
+{
+    let metadata = symlink_metadata(path)?;
+    let file_type = metadata.file_type();
+
+    let digest = if file_type.is_symlink() {
+        let target = std::fs::read_link(path)?;
+        T::hash(target.to_string_lossy().as_bytes())
+    } else if file_type.is_dir() {
+        let mut entries: Vec<_> = read_dir(path)?.collect::<std::io::Result<_>>()?;
+        entries.sort_by_key(std::fs::DirEntry::file_name);
+
+        let mut hash = T::default();
+        for entry in entries {
+            let entry_type = if entry.file_type()?.is_symlink() {
+                TYPE_SYMLINK
+            } else if entry.file_type()?.is_dir() {
+                TYPE_DIR
+            } else {
+                TYPE_FILE
+            };
+            let entry_mode = mode_of(&entry.metadata()?);
+            let child_digest = node::<T>(&entry.path(), nodes)?;
+
+            entry.file_name().to_string_lossy().as_bytes().hash_with(&mut hash);
+            [0u8].hash_with(&mut hash);
+            [entry_type].hash_with(&mut hash);
+            entry_mode.to_le_bytes().hash_with(&mut hash);
+            child_digest.as_bytes().hash_with(&mut hash);
+        }
+        hash.digest()
+    } else {
+        let mut path = path;
+        path.chksum::<T>()?
+    };
+
+    nodes.push((path.to_path_buf(), digest.as_bytes().to_vec()));
+    Ok(digest)
+}
+
+/// Computes the Merkle digest of the filesystem tree rooted at `path`.
+///
+/// Also returns a `(path, digest bytes)` entry for every node in the tree — including `path`
+/// itself — so callers can diff two trees and locate the changed subtrees without re-hashing
+/// anything.
+pub fn merkle_chksum<T>(path: impl AsRef<Path>) -> Result<(T::Digest, Vec<(PathBuf, Vec<u8>)>)>
+where
+    T: Hash,
+    T::Digest: AsRef<[u8]>,
+{
+    let mut nodes = Vec::new();
+    let digest = node::<T>(path.as_ref(), &mut nodes)?;
+    Ok((digest, nodes))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::test_util::TestHash;
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("chksum-core-merkle-test-{name}-{}", std::process::id()));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+
+        fn path(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn file_digest_matches_a_plain_hash_of_its_bytes() {
+        let dir = TempDir::new("file");
+        let file_path = dir.path().join("a.txt");
+        fs::write(&file_path, b"hello").unwrap();
+
+        let (digest, nodes) = merkle_chksum::<TestHash>(&file_path).unwrap();
+
+        assert_eq!(digest, TestHash::hash(b"hello".as_slice()));
+        assert_eq!(nodes, vec![(file_path, digest.as_bytes().to_vec())]);
+    }
+
+    #[test]
+    fn directory_digest_includes_every_node() {
+        let dir = TempDir::new("tree");
+        fs::write(dir.path().join("a.txt"), b"a").unwrap();
+        fs::create_dir(dir.path().join("sub")).unwrap();
+        fs::write(dir.path().join("sub/b.txt"), b"b").unwrap();
+
+        let (_, nodes) = merkle_chksum::<TestHash>(dir.path()).unwrap();
+        let paths: std::collections::HashSet<_> = nodes.iter().map(|(path, _)| path.clone()).collect();
+
+        assert!(paths.contains(dir.path()));
+        assert!(paths.contains(&dir.path().join("a.txt")));
+        assert!(paths.contains(&dir.path().join("sub")));
+        assert!(paths.contains(&dir.path().join("sub/b.txt")));
+    }
+
+    #[test]
+    fn renaming_an_entry_changes_the_parent_digest() {
+        let original = TempDir::new("rename-original");
+        fs::write(original.path().join("a.txt"), b"content").unwrap();
+        let (original_digest, _) = merkle_chksum::<TestHash>(original.path()).unwrap();
+
+        let renamed = TempDir::new("rename-renamed");
+        fs::write(renamed.path().join("b.txt"), b"content").unwrap();
+        let (renamed_digest, _) = merkle_chksum::<TestHash>(renamed.path()).unwrap();
+
+        assert_ne!(original_digest, renamed_digest);
+    }
+
+    #[test]
+    fn identical_trees_hash_identically_regardless_of_creation_order() {
+        let forwards = TempDir::new("order-forwards");
+        fs::write(forwards.path().join("a.txt"), b"a").unwrap();
+        fs::write(forwards.path().join("b.txt"), b"b").unwrap();
+
+        let backwards = TempDir::new("order-backwards");
+        fs::write(backwards.path().join("b.txt"), b"b").unwrap();
+        fs::write(backwards.path().join("a.txt"), b"a").unwrap();
+
+        let (forwards_digest, _) = merkle_chksum::<TestHash>(forwards.path()).unwrap();
+        let (backwards_digest, _) = merkle_chksum::<TestHash>(backwards.path()).unwrap();
+
+        assert_eq!(forwards_digest, backwards_digest);
+    }
+}
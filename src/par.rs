@@ -0,0 +1,73 @@
+//! Parallel directory hashing with a combining tree.
+//!
+//! [`par_chksum`] hashes the regular files of a directory tree concurrently on a `rayon` thread
+//! pool, computing a standalone digest per file (via [`Hash::hash`]) rather than streaming
+//! bytes into one shared hash state. The per-file digests are then folded into each directory's
+//! digest in sorted-by-path order, so the result is reproducible regardless of which file
+//! finishes hashing first. This pairs with the [`crate::merkle`] approach of binding a name into
+//! every combined digest.
+
+use std::fs::{metadata, read, read_dir, DirEntry};
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+
+use crate::{Error, Hash, Hashable, Result};
+
+fn file_digest<T>(path: &Path) -> Result<T::Digest>
+where
+    T: Hash,
+{
+    let bytes = read(path).map_err(Error::Io)?;
+    Ok(T::hash(bytes))
+}
+
+fn combine<T>(entries: &[(PathBuf, T::Digest)]) -> T::Digest
+where
+    T: Hash,
+    T::Digest: AsRef<[u8]>,
+{
+    let mut hash = T::default();
+    for (path, digest) in entries {
+        path.to_string_lossy().as_bytes().hash_with(&mut hash);
+        digest.as_bytes().hash_with(&mut hash);
+    }
+    hash.digest()
+}
+
+/// Computes the hash of `path`, hashing independent files concurrently on a `rayon` thread pool
+/// and folding the results into a deterministic, order-independent digest.
+pub fn par_chksum<T>(path: impl AsRef<Path>) -> Result<T::Digest>
+where
+    T: Hash + Send,
+    T::Digest: Send + AsRef<[u8]>,
+{
+    let path = path.as_ref();
+    let path_metadata = metadata(path).map_err(Error::Io)?;
+    if !path_metadata.is_dir() {
+        return file_digest::<T>(path);
+    }
+
+    let mut entries: Vec<DirEntry> = read_dir(path)
+        .map_err(Error::Io)?
+        .collect::<std::io::Result<_>>()
+        .map_err(Error::Io)?;
+    entries.sort_by_key(DirEntry::path);
+
+    let digests: Vec<(PathBuf, T::Digest)> = entries
+        .into_par_iter()
+        .map(|entry| -> Result<(PathBuf, T::Digest)> {
+            let entry_path = entry.path();
+            // `DirEntry::file_type` does not follow symlinks, unlike the `Chksumable` impl for
+            // `Path`; a symlink to a directory must still be recursed into
+            let digest = if metadata(&entry_path).map_err(Error::Io)?.is_dir() {
+                par_chksum::<T>(&entry_path)?
+            } else {
+                file_digest::<T>(&entry_path)?
+            };
+            Ok((entry_path, digest))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(combine::<T>(&digests))
+}
@@ -0,0 +1,328 @@
+//! Deterministic hashing of tar archives based on their logical contents.
+//!
+//! A plain byte-for-byte hash of a `.tar` file is sensitive to entry ordering and to the
+//! padding bytes the format requires, so two archives with identical contents can hash
+//! differently. [`Tar`] instead parses the USTAR block structure, sorts entries by path, and
+//! feeds a canonical representation of each entry into the hash.
+
+use std::io::Read;
+
+use crate::{Chksumable, Error, Hash, Hashable, Result};
+
+const BLOCK_SIZE: usize = 512;
+
+const TYPEFLAG_EXTENDED: u8 = b'x';
+const TYPEFLAG_GLOBAL_EXTENDED: u8 = b'g';
+const TYPEFLAG_GNU_LONGNAME: u8 = b'L';
+
+struct Entry {
+    path: String,
+    typeflag: u8,
+    mode: u32,
+    data: Vec<u8>,
+}
+
+fn read_block(reader: &mut impl Read) -> Result<Option<[u8; BLOCK_SIZE]>> {
+    let mut block = [0; BLOCK_SIZE];
+    let mut read = 0;
+    while read < BLOCK_SIZE {
+        let length = reader.read(&mut block[read..]).map_err(Error::Io)?;
+        if length == 0 {
+            break;
+        }
+        read += length;
+    }
+    if read == 0 {
+        Ok(None)
+    } else if read < BLOCK_SIZE {
+        Err(Error::Tar("truncated block".into()))
+    } else {
+        Ok(Some(block))
+    }
+}
+
+fn parse_octal(field: &[u8]) -> Result<u64> {
+    // GNU tar extension: a high bit set on the first byte marks a big-endian binary value
+    // (with that bit masked off) occupying the rest of the field, used when a size or mtime
+    // does not fit in the 11-digit octal text representation
+    if let Some((&first, rest)) = field.split_first() {
+        if first & 0x80 != 0 {
+            let mut value: u64 = u64::from(first & 0x7F);
+            for &byte in rest {
+                value = (value << 8) | u64::from(byte);
+            }
+            return Ok(value);
+        }
+    }
+
+    let field = field
+        .iter()
+        .copied()
+        .take_while(|&byte| byte != 0)
+        .collect::<Vec<_>>();
+    let text = std::str::from_utf8(&field)
+        .map_err(|_| Error::Tar("non-utf8 numeric field".into()))?
+        .trim();
+    if text.is_empty() {
+        return Ok(0);
+    }
+    u64::from_str_radix(text, 8).map_err(|_| Error::Tar("invalid octal field".into()))
+}
+
+fn parse_cstr(field: &[u8]) -> String {
+    let end = field.iter().position(|&byte| byte == 0).unwrap_or(field.len());
+    String::from_utf8_lossy(&field[..end]).into_owned()
+}
+
+fn blocks_for(size: u64) -> u64 {
+    size.div_ceil(BLOCK_SIZE as u64)
+}
+
+fn read_data(reader: &mut impl Read, size: u64) -> Result<Vec<u8>> {
+    let mut data = Vec::with_capacity(size as usize);
+    let mut remaining = blocks_for(size);
+    let mut unread = size;
+    while remaining > 0 {
+        let block = read_block(reader)?.ok_or_else(|| Error::Tar("truncated entry data".into()))?;
+        let take = unread.min(BLOCK_SIZE as u64) as usize;
+        data.extend_from_slice(&block[..take]);
+        unread -= take as u64;
+        remaining -= 1;
+    }
+    Ok(data)
+}
+
+/// Parses `reader` as a tar archive and returns its entries sorted by path, with GNU longname
+/// and PAX extended headers already folded into the regular entries that follow them.
+fn read_entries(reader: &mut impl Read) -> Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+    let mut pending_long_name: Option<String> = None;
+    let mut pending_size: Option<u64> = None;
+    loop {
+        let Some(header) = read_block(reader)? else {
+            break;
+        };
+        if header.iter().all(|&byte| byte == 0) {
+            // a zero block marks the end of the archive (there are normally two in a row)
+            break;
+        }
+
+        let name = parse_cstr(&header[0..100]);
+        let mode = parse_octal(&header[100..108])? as u32;
+        let size = parse_octal(&header[124..136])?;
+        let typeflag = header[156];
+
+        match typeflag {
+            TYPEFLAG_GNU_LONGNAME => {
+                let data = read_data(reader, size)?;
+                pending_long_name = Some(parse_cstr(&data));
+            },
+            TYPEFLAG_EXTENDED | TYPEFLAG_GLOBAL_EXTENDED => {
+                // PAX extended headers carry "<len> <key>=<value>\n" records; `path` and `size`
+                // are the only ones relevant to the canonical digest computed here.
+                let data = read_data(reader, size)?;
+                let text = String::from_utf8_lossy(&data);
+                for record in text.split('\n').filter(|record| !record.is_empty()) {
+                    if let Some((_, rest)) = record.split_once(' ') {
+                        if let Some(value) = rest.strip_prefix("path=") {
+                            pending_long_name = Some(value.to_owned());
+                        } else if let Some(value) = rest.strip_prefix("size=") {
+                            pending_size = value.parse().ok();
+                        }
+                    }
+                }
+            },
+            _ => {
+                let size = pending_size.take().unwrap_or(size);
+                let data = read_data(reader, size)?;
+                let path = pending_long_name.take().unwrap_or(name);
+                entries.push(Entry {
+                    path,
+                    typeflag,
+                    mode,
+                    data,
+                });
+            },
+        }
+    }
+    entries.sort_by(|left, right| left.path.cmp(&right.path));
+    Ok(entries)
+}
+
+fn hash_entries<H>(entries: &[Entry], hash: &mut H)
+where
+    H: Hash,
+{
+    for entry in entries {
+        entry.path.as_bytes().hash_with(hash);
+        [0u8].hash_with(hash);
+        [entry.typeflag].hash_with(hash);
+        entry.mode.to_be_bytes().hash_with(hash);
+        (entry.data.len() as u64).to_be_bytes().hash_with(hash);
+        entry.data.hash_with(hash);
+    }
+}
+
+/// Wraps a reader containing a tar archive, so that its logical contents — rather than the raw
+/// padded bytes — are fed into the hash.
+///
+/// Entries are sorted by path and described by a canonical `path, typeflag, mode, size, data`
+/// tuple, so two archives that differ only in entry ordering or trailing padding hash
+/// identically.
+pub struct Tar<R> {
+    reader: R,
+}
+
+impl<R> Tar<R>
+where
+    R: Read,
+{
+    /// Wraps `reader` so that it is parsed and canonicalized as a tar archive when checksummed.
+    #[must_use]
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+}
+
+impl<R> Chksumable for Tar<R>
+where
+    R: Read,
+{
+    fn chksum_with<H>(&mut self, hash: &mut H) -> Result<()>
+    where
+        H: Hash,
+    {
+        let entries = read_entries(&mut self.reader)?;
+        hash_entries(&entries, hash);
+        Ok(())
+    }
+}
+
+/// Computes the hash of the tar archive read from `reader`.
+///
+/// See [`Tar`] for how the archive is canonicalized before hashing.
+pub fn chksum_tar<T, R>(reader: R) -> Result<T::Digest>
+where
+    T: Hash,
+    R: Read,
+{
+    Tar::new(reader).chksum::<T>()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::test_util::TestHash;
+
+    fn octal_field(value: u64, width: usize) -> Vec<u8> {
+        let digits = width - 1;
+        let mut field = format!("{value:0digits$o}").into_bytes();
+        field.push(0);
+        field.resize(width, 0);
+        field
+    }
+
+    fn header(name: &str, size: u64, typeflag: u8) -> [u8; BLOCK_SIZE] {
+        let mut block = [0; BLOCK_SIZE];
+        block[0..name.len()].copy_from_slice(name.as_bytes());
+        block[100..108].copy_from_slice(&octal_field(0o644, 8));
+        block[124..136].copy_from_slice(&octal_field(size, 12));
+        block[156] = typeflag;
+        block
+    }
+
+    fn pad_to_block(data: &mut Vec<u8>) {
+        let remainder = data.len() % BLOCK_SIZE;
+        if remainder != 0 {
+            data.extend(std::iter::repeat(0).take(BLOCK_SIZE - remainder));
+        }
+    }
+
+    fn archive(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut archive = Vec::new();
+        for (name, content) in entries {
+            archive.extend_from_slice(&header(name, content.len() as u64, b'0'));
+            archive.extend_from_slice(content);
+            pad_to_block(&mut archive);
+        }
+        archive.extend(std::iter::repeat(0).take(BLOCK_SIZE * 2));
+        archive
+    }
+
+    #[test]
+    fn plain_entries_are_sorted_by_path() {
+        let data = archive(&[("b.txt", b"second"), ("a.txt", b"first")]);
+        let entries = read_entries(&mut Cursor::new(data)).unwrap();
+        let paths: Vec<_> = entries.iter().map(|entry| entry.path.as_str()).collect();
+        assert_eq!(paths, ["a.txt", "b.txt"]);
+    }
+
+    #[test]
+    fn chksum_tar_is_order_independent() {
+        let forwards = archive(&[("a.txt", b"hello"), ("b.txt", b"world")]);
+        let backwards = archive(&[("b.txt", b"world"), ("a.txt", b"hello")]);
+        let forwards = chksum_tar::<TestHash, _>(Cursor::new(forwards)).unwrap();
+        let backwards = chksum_tar::<TestHash, _>(Cursor::new(backwards)).unwrap();
+        assert_eq!(forwards, backwards);
+    }
+
+    #[test]
+    fn gnu_longname_overrides_the_ustar_name_field() {
+        let long_name = "a/very/long/path/that/would/not/fit/in/the/ustar/name/field.txt";
+        let mut name_data = long_name.as_bytes().to_vec();
+        name_data.push(0);
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&header("", name_data.len() as u64, TYPEFLAG_GNU_LONGNAME));
+        data.extend_from_slice(&name_data);
+        pad_to_block(&mut data);
+        data.extend_from_slice(&header("ignored.txt", 5, b'0'));
+        data.extend_from_slice(b"hello");
+        pad_to_block(&mut data);
+        data.extend(std::iter::repeat(0).take(BLOCK_SIZE * 2));
+
+        let entries = read_entries(&mut Cursor::new(data)).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, long_name);
+    }
+
+    #[test]
+    fn pax_header_overrides_path_and_size() {
+        let pax_path = "pax/overridden/name.bin";
+        let content = b"exactly11B!";
+        assert_eq!(content.len(), 11);
+        let pax_data = format!("0 path={pax_path}\n0 size={}\n", content.len());
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&header("", pax_data.len() as u64, TYPEFLAG_EXTENDED));
+        data.extend_from_slice(pax_data.as_bytes());
+        pad_to_block(&mut data);
+        // the ustar header's own size is wrong on purpose; the PAX `size=` record must win
+        data.extend_from_slice(&header("ignored.bin", 1, b'0'));
+        data.extend_from_slice(content);
+        pad_to_block(&mut data);
+        data.extend(std::iter::repeat(0).take(BLOCK_SIZE * 2));
+
+        let entries = read_entries(&mut Cursor::new(data)).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, pax_path);
+        assert_eq!(entries[0].data.as_slice(), content.as_slice());
+    }
+
+    #[test]
+    fn base_256_size_field_is_decoded() {
+        let mut size_field = [0u8; 12];
+        size_field[0] = 0x80;
+        size_field[11] = 5;
+        assert_eq!(parse_octal(&size_field).unwrap(), 5);
+    }
+
+    #[test]
+    fn truncated_block_is_an_error() {
+        let data = vec![1; 100];
+        let result = read_entries(&mut Cursor::new(data));
+        assert!(matches!(result, Err(Error::Tar(_))));
+    }
+}
@@ -0,0 +1,51 @@
+//! A minimal, non-cryptographic [`Hash`] implementation used only by this crate's own unit
+//! tests, so the feature-gated modules can exercise [`Chksumable`](crate::Chksumable) and
+//! friends without depending on one of the `chksum-*` algorithm crates.
+
+use std::fmt;
+
+use crate::{Digest, Hash};
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct TestDigest([u8; 8]);
+
+impl fmt::Display for TestDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl AsRef<[u8]> for TestDigest {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Digest for TestDigest {}
+
+#[derive(Default)]
+pub(crate) struct TestHash(u64);
+
+impl Hash for TestHash {
+    type Digest = TestDigest;
+
+    fn update<T>(&mut self, data: T)
+    where
+        T: AsRef<[u8]>,
+    {
+        for &byte in data.as_ref() {
+            self.0 = self.0.wrapping_mul(31).wrapping_add(u64::from(byte));
+        }
+    }
+
+    fn reset(&mut self) {
+        self.0 = 0;
+    }
+
+    fn digest(&self) -> Self::Digest {
+        TestDigest(self.0.to_be_bytes())
+    }
+}
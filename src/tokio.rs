@@ -91,6 +91,197 @@ impl_async_chksumable!(ReadDir, &mut ReadDir => {
     }
 });
 
+#[cfg(feature = "decompression")]
+mod decompress {
+    //! Async mirror of [`crate::decompress`], backed by `async-compression`'s Tokio decoders.
+
+    use async_compression::tokio::bufread::{BzDecoder, GzipDecoder, XzDecoder, ZstdDecoder};
+    use tokio::io::{AsyncBufReadExt as _, AsyncRead, AsyncReadExt as _, BufReader};
+
+    use crate::{AsyncChksumable, Error, Hash, Hashable, Result};
+
+    const GZIP_MAGIC: [u8; 2] = [0x1F, 0x8B];
+    const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+    const BZIP2_MAGIC: [u8; 3] = [0x42, 0x5A, 0x68];
+    const XZ_MAGIC: [u8; 5] = [0xFD, 0x37, 0x7A, 0x58, 0x5A];
+
+    enum Decoder<R: AsyncRead + Unpin> {
+        Gzip(GzipDecoder<R>),
+        Zstd(ZstdDecoder<R>),
+        Bzip2(BzDecoder<R>),
+        Xz(XzDecoder<R>),
+        Raw(R),
+    }
+
+    /// Classifies a read failure from a (possibly decompressing) reader as either a rejected
+    /// compressed stream or a genuine I/O failure.
+    ///
+    /// `async-compression`'s decoders surface malformed input as `io::ErrorKind::InvalidData`;
+    /// anything else (a broken pipe, a failing disk read, ...) is a real I/O error and must not
+    /// be mislabeled as a decompression failure.
+    fn classify_error(error: std::io::Error) -> Error {
+        if error.kind() == std::io::ErrorKind::InvalidData {
+            Error::Decompression(error)
+        } else {
+            Error::Io(error)
+        }
+    }
+
+    /// Wraps an async reader so that it is transparently decompressed when checksummed.
+    ///
+    /// See [`crate::decompress::Decompressed`] for the list of supported formats and the
+    /// fallback behavior.
+    pub struct Decompressed<R> {
+        reader: Option<BufReader<R>>,
+    }
+
+    impl<R> Decompressed<R>
+    where
+        R: AsyncRead + Unpin,
+    {
+        /// Wraps `reader` so that it is transparently decompressed when checksummed.
+        #[must_use]
+        pub fn new(reader: R) -> Self {
+            Self {
+                reader: Some(BufReader::new(reader)),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl<R> AsyncChksumable for Decompressed<R>
+    where
+        R: AsyncRead + Unpin + Send,
+    {
+        async fn chksum_with<H>(&mut self, hash: &mut H) -> Result<()>
+        where
+            H: Hash + Send,
+        {
+            let mut reader = self.reader.take().expect("reader already consumed");
+            let buffer = reader.fill_buf().await.map_err(Error::Io)?;
+            let mut decoder = if buffer.starts_with(&GZIP_MAGIC) {
+                Decoder::Gzip(GzipDecoder::new(reader))
+            } else if buffer.starts_with(&ZSTD_MAGIC) {
+                Decoder::Zstd(ZstdDecoder::new(reader))
+            } else if buffer.starts_with(&BZIP2_MAGIC) {
+                Decoder::Bzip2(BzDecoder::new(reader))
+            } else if buffer.starts_with(&XZ_MAGIC) {
+                Decoder::Xz(XzDecoder::new(reader))
+            } else {
+                Decoder::Raw(reader)
+            };
+
+            let mut buffer = [0; 8192];
+            loop {
+                let length = match &mut decoder {
+                    Decoder::Gzip(reader) => reader.read(&mut buffer).await,
+                    Decoder::Zstd(reader) => reader.read(&mut buffer).await,
+                    Decoder::Bzip2(reader) => reader.read(&mut buffer).await,
+                    Decoder::Xz(reader) => reader.read(&mut buffer).await,
+                    Decoder::Raw(reader) => reader.read(&mut buffer).await,
+                }
+                .map_err(classify_error)?;
+                if length == 0 {
+                    break;
+                }
+                buffer[..length].hash_with(hash);
+            }
+            Ok(())
+        }
+    }
+
+    /// Computes the hash of the given async reader, transparently decompressing it first.
+    pub async fn async_chksum_decompressed<T, R>(reader: R) -> Result<T::Digest>
+    where
+        T: Hash + Send,
+        R: AsyncRead + Unpin + Send,
+    {
+        Decompressed::new(reader).chksum::<T>().await
+    }
+}
+
+#[cfg(feature = "decompression")]
+pub use decompress::{async_chksum_decompressed, Decompressed as AsyncDecompressed};
+
+#[cfg(feature = "rayon")]
+mod par {
+    //! Async mirror of [`crate::par`], using `tokio::spawn` in place of a `rayon` thread pool.
+
+    use std::future::Future;
+    use std::path::PathBuf;
+    use std::pin::Pin;
+
+    use tokio::fs::{metadata, read, read_dir, DirEntry};
+
+    use crate::{Error, Hash, Hashable, Result};
+
+    fn combine<T>(entries: &[(PathBuf, T::Digest)]) -> T::Digest
+    where
+        T: Hash,
+        T::Digest: AsRef<[u8]>,
+    {
+        let mut hash = T::default();
+        for (path, digest) in entries {
+            path.to_string_lossy().as_bytes().hash_with(&mut hash);
+            digest.as_bytes().hash_with(&mut hash);
+        }
+        hash.digest()
+    }
+
+    /// Computes the hash of `path`, hashing independent files concurrently via `tokio::spawn`
+    /// and folding the results into a deterministic, order-independent digest.
+    pub fn par_chksum<T>(path: PathBuf) -> Pin<Box<dyn Future<Output = Result<T::Digest>> + Send>>
+    where
+        T: Hash + Send + 'static,
+        T::Digest: Send + AsRef<[u8]> + 'static,
+    {
+        Box::pin(async move {
+            let file_metadata = metadata(&path).await.map_err(Error::Io)?;
+            if !file_metadata.is_dir() {
+                let bytes = read(&path).await.map_err(Error::Io)?;
+                return Ok(T::hash(bytes));
+            }
+
+            let mut entries = Vec::new();
+            let mut reader = read_dir(&path).await.map_err(Error::Io)?;
+            while let Some(entry) = reader.next_entry().await.map_err(Error::Io)? {
+                entries.push(entry);
+            }
+            entries.sort_by_key(DirEntry::path);
+
+            let tasks: Vec<_> = entries
+                .into_iter()
+                .map(|entry| {
+                    tokio::spawn(async move {
+                        let entry_path = entry.path();
+                        // `DirEntry::file_type` does not follow symlinks, unlike the
+                        // `AsyncChksumable` impl for `Path`; a symlink to a directory must
+                        // still be recursed into
+                        let is_dir = metadata(&entry_path).await.map_err(Error::Io)?.is_dir();
+                        let digest = if is_dir {
+                            par_chksum::<T>(entry_path.clone()).await?
+                        } else {
+                            let bytes = read(&entry_path).await.map_err(Error::Io)?;
+                            T::hash(bytes)
+                        };
+                        Result::Ok((entry_path, digest))
+                    })
+                })
+                .collect();
+
+            let mut digests = Vec::with_capacity(tasks.len());
+            for task in tasks {
+                digests.push(task.await.expect("par_chksum task panicked")?);
+            }
+
+            Ok(combine::<T>(&digests))
+        })
+    }
+}
+
+#[cfg(feature = "rayon")]
+pub use par::par_chksum as async_par_chksum;
+
 // TODO: missing `&Stdin` implementation
 impl_async_chksumable!(Stdin, &mut Stdin => {
     async fn chksum_with<H>(&mut self, hash: &mut H) -> Result<()>